@@ -7,6 +7,18 @@ use gfx_hal::buffer::{CreationError as BufferCreationError, Usage as BufferUsage
 use gfx_hal::format::Format;
 use gfx_hal::image::{CreationError as ImageCreationError, Kind, Level, Usage as ImageUsage,
                      Tiling, ViewCapabilities};
+use gfx_hal::memory::Properties;
+use gfx_hal::mapping::Error as MappingError;
+use gfx_hal::command::{BufferCopy, CommandBuffer};
+use gfx_hal::Transfer;
+
+#[cfg(unix)]
+use std::os::unix::io::RawFd;
+use std::rc::Rc;
+
+use gfx_hal::memory::{Access, Requirements};
+use gfx_hal::pso::PipelineStage;
+use gfx_hal::queue::QueueFamilyId;
 
 use block::Block;
 
@@ -90,6 +102,106 @@ pub trait Factory<B: Backend> {
     /// - `device`: device the image was created on
     /// - `image`: the image to destroy
     fn destroy_image(&mut self, device: &B::Device, image: Self::Image);
+
+    /// Create a buffer, picking memory properties from a backend-agnostic
+    /// `MemoryUsage` hint instead of a raw, allocator-specific `BufferRequest`.
+    ///
+    /// ### Parameters
+    ///
+    /// - `device`: device to create the buffer on
+    /// - `usage`: hint describing how the buffer's memory will be accessed
+    /// - `size`: size in bytes of the buffer
+    /// - `buffer_usage`: hal buffer `Usage`
+    fn create_buffer_usage(
+        &mut self,
+        device: &B::Device,
+        usage: MemoryUsage,
+        size: u64,
+        buffer_usage: BufferUsage,
+    ) -> Result<Self::Buffer, Self::Error>
+    where
+        Self::BufferRequest: From<MemoryUsage>,
+    {
+        self.create_buffer(device, Self::BufferRequest::from(usage), size, buffer_usage)
+    }
+
+    /// Create an image, picking memory properties from a backend-agnostic
+    /// `MemoryUsage` hint instead of a raw, allocator-specific `ImageRequest`.
+    ///
+    /// ### Parameters
+    ///
+    /// - `device`: device to create the image on
+    /// - `usage`: hint describing how the image's memory will be accessed
+    /// - `kind`: `Kind` of texture storage to allocate
+    /// - `level`: mipmap level
+    /// - `format`: texture format
+    /// - `image_usage`: hal image usage
+    fn create_image_usage(
+        &mut self,
+        device: &B::Device,
+        usage: MemoryUsage,
+        kind: Kind,
+        level: Level,
+        format: Format,
+        tiling: Tiling,
+        image_usage: ImageUsage,
+        view_caps: ViewCapabilities,
+    ) -> Result<Self::Image, Self::Error>
+    where
+        Self::ImageRequest: From<MemoryUsage>,
+    {
+        self.create_image(
+            device,
+            Self::ImageRequest::from(usage),
+            kind,
+            level,
+            format,
+            tiling,
+            image_usage,
+            view_caps,
+        )
+    }
+}
+
+/// Backend-agnostic hint describing how a resource's memory will be accessed.
+///
+/// Allocators opt in by implementing `From<MemoryUsage>` for their own `Request`
+/// type, typically by consulting `MemoryUsage::properties`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryUsage {
+    /// GPU-private data the host never touches, e.g. render targets.
+    Data,
+    /// Written by the host once (or rarely), read by the device, e.g. staging
+    /// buffers.
+    Upload,
+    /// Written by the device, read back by the host, e.g. readback buffers.
+    Download,
+    /// Written by the host frequently, read by the device, e.g. per-frame
+    /// uniform buffers.
+    Dynamic,
+}
+
+impl MemoryUsage {
+    /// Desired and preferred `Properties` for this usage.
+    ///
+    /// ### Parameters
+    ///
+    /// returns `(desired, preferred)`: `desired` must be a subset of a candidate
+    /// memory type's properties to be usable; `preferred` breaks ties between
+    /// otherwise-usable types.
+    pub fn properties(&self) -> (Properties, Properties) {
+        match *self {
+            MemoryUsage::Data => (Properties::DEVICE_LOCAL, Properties::empty()),
+            MemoryUsage::Upload => (Properties::CPU_VISIBLE, Properties::COHERENT),
+            MemoryUsage::Download => {
+                (Properties::CPU_VISIBLE, Properties::CPU_CACHED)
+            }
+            MemoryUsage::Dynamic => (
+                Properties::CPU_VISIBLE,
+                Properties::COHERENT | Properties::DEVICE_LOCAL,
+            ),
+        }
+    }
 }
 
 /// Memory resource produced by the blanket `MemoryAllocator` as `Factory` implementation.
@@ -102,6 +214,12 @@ pub trait Factory<B: Backend> {
 pub struct Item<I, T> {
     raw: I,
     block: T,
+    tracked: Vec<(Range<u64>, AccessState)>,
+    /// Base pointer of a persistent, whole-block HAL mapping, if one is currently
+    /// active. Kept across calls to `map()` so that mapping the same block twice in
+    /// a row reuses one `map_memory` call instead of mapping/unmapping each time,
+    /// since many allocators forbid double-mapping a `B::Memory`.
+    persistent_map: Option<*mut u8>,
 }
 
 impl<I, T> Item<I, T> {
@@ -118,6 +236,385 @@ impl<I, T> Item<I, T> {
     }
 }
 
+impl<I, T> Item<I, T>
+where
+    T: Block,
+{
+    /// Map a range of the item's memory for host access, reusing a persistent
+    /// HAL mapping across calls. Call `unmap` to release it.
+    ///
+    /// ### Parameters
+    ///
+    /// - `device`: device the item was created on
+    /// - `range`: byte range, relative to the block, to map
+    pub fn map<'a, B>(
+        &'a mut self,
+        device: &'a B::Device,
+        range: Range<u64>,
+    ) -> Result<MappedRange<'a, B>, MemoryError>
+    where
+        B: Backend<Memory = T::Memory>,
+    {
+        let block_range = self.block.range();
+        let start = block_range.start + range.start;
+        let end = (block_range.start + range.end).min(block_range.end);
+        assert!(start <= end, "mapped range must not start after it ends");
+
+        let base_ptr = match self.persistent_map {
+            Some(ptr) => ptr,
+            None => {
+                let ptr = device
+                    .map_memory(self.block.memory(), block_range.clone())
+                    .map_err(MemoryError::from)?;
+                self.persistent_map = Some(ptr);
+                ptr
+            }
+        };
+        let ptr = unsafe { base_ptr.offset((start - block_range.start) as isize) };
+
+        Ok(MappedRange {
+            device,
+            memory: self.block.memory(),
+            range: start..end,
+            ptr,
+            coherent: self.block.properties().contains(Properties::COHERENT),
+            flush_range: None,
+        })
+    }
+
+    /// Release the persistent HAL mapping established by `map`, if one is active.
+    /// A no-op if the item is not currently mapped.
+    pub fn unmap<B>(&mut self, device: &B::Device)
+    where
+        B: Backend<Memory = T::Memory>,
+    {
+        if self.persistent_map.take().is_some() {
+            device.unmap_memory(self.block.memory());
+        }
+    }
+}
+
+/// A guard over a host-visible range of device memory, obtained from `Item::map`.
+///
+/// Does not unmap on drop: the underlying HAL mapping is persistent across calls
+/// to `Item::map` and is only released by `Item::unmap`. If the backing memory
+/// type is not `COHERENT`, any range written to via `write()` is flushed
+/// automatically on drop (or earlier, via `flush()`), and `read()` invalidates the
+/// mapped range before reading from it.
+pub struct MappedRange<'a, B: Backend> {
+    device: &'a B::Device,
+    memory: &'a B::Memory,
+    range: Range<u64>,
+    ptr: *mut u8,
+    coherent: bool,
+    flush_range: Option<Range<u64>>,
+}
+
+impl<'a, B: Backend> MappedRange<'a, B> {
+    /// Get the mapped memory as a byte slice.
+    ///
+    /// If the memory is not `COHERENT`, this invalidates the mapped range first so
+    /// that any device-side writes become visible.
+    pub fn read(&mut self) -> Result<&[u8], MemoryError> {
+        if !self.coherent {
+            self.device
+                .invalidate_mapped_memory_ranges(Some((self.memory, self.range.clone())))
+                .map_err(MemoryError::from)?;
+        }
+        Ok(unsafe {
+            ::std::slice::from_raw_parts(self.ptr, (self.range.end - self.range.start) as usize)
+        })
+    }
+
+    /// Get the mapped memory as a mutable byte slice.
+    ///
+    /// If the memory is not `COHERENT`, the full mapped range is recorded to be
+    /// flushed on `flush()` or on drop.
+    pub fn write(&mut self) -> &mut [u8] {
+        if !self.coherent {
+            self.flush_range = Some(self.range.clone());
+        }
+        unsafe {
+            ::std::slice::from_raw_parts_mut(self.ptr, (self.range.end - self.range.start) as usize)
+        }
+    }
+
+    /// Explicitly flush the touched sub-range to the device, if the memory is not
+    /// `COHERENT`. A no-op otherwise, or if nothing has been written since the last
+    /// flush.
+    pub fn flush(&mut self) -> Result<(), MemoryError> {
+        if let Some(range) = self.flush_range.take() {
+            self.device
+                .flush_mapped_memory_ranges(Some((self.memory, range)))
+                .map_err(MemoryError::from)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a, B: Backend> Drop for MappedRange<'a, B> {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+impl From<MappingError> for MemoryError {
+    fn from(error: MappingError) -> Self {
+        MemoryError::MappingError(error)
+    }
+}
+
+/// A device-side buffer-to-buffer copy, from a temporary staging buffer into the
+/// real destination, deferred from `FactoryInit::create_buffer_init` because the
+/// destination memory was not host-visible.
+///
+/// `staging` must be kept alive until `record`'s command buffer has finished
+/// executing on the device, and destroyed afterwards via `Factory::destroy_buffer`.
+pub struct PendingBufferUpload<Buf> {
+    /// Temporary `Upload`-usage buffer holding the data to be copied to the
+    /// destination.
+    pub staging: Buf,
+    regions: Vec<BufferCopy>,
+}
+
+impl<Buf> PendingBufferUpload<Buf> {
+    /// Record the deferred copy into `dst_buffer`.
+    pub fn record<B, C>(&self, cmd: &mut CommandBuffer<B, C>, dst_buffer: &B::Buffer)
+    where
+        B: Backend,
+        Buf: Borrow<B::Buffer>,
+        C: Transfer,
+    {
+        cmd.copy_buffer(self.staging.borrow(), dst_buffer, &self.regions);
+    }
+}
+
+/// A device-side buffer-to-image copy, from a temporary staging buffer into the
+/// real destination, deferred from `FactoryInit::create_image_init` because images
+/// are never host-visible.
+///
+/// `staging` must be kept alive until `record_image`'s command buffer has finished
+/// executing on the device, and destroyed afterwards via `Factory::destroy_buffer`.
+pub struct PendingImageUpload<Buf> {
+    /// Temporary `Upload`-usage buffer holding the data to be copied to the
+    /// destination.
+    pub staging: Buf,
+    regions: Vec<gfx_hal::command::BufferImageCopy>,
+}
+
+impl<Buf> PendingImageUpload<Buf> {
+    /// Record the deferred copy into `dst_image`, which must currently be in
+    /// `dst_layout`.
+    pub fn record_image<B, C>(
+        &self,
+        cmd: &mut CommandBuffer<B, C>,
+        dst_image: &B::Image,
+        dst_layout: gfx_hal::image::Layout,
+    ) where
+        B: Backend,
+        Buf: Borrow<B::Buffer>,
+        C: Transfer,
+    {
+        cmd.copy_buffer_to_image(self.staging.borrow(), dst_image, dst_layout, &self.regions);
+    }
+}
+
+/// The last recorded way a sub-range of an `Item`'s resource was accessed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AccessState {
+    /// Kind of access (read, write, or both).
+    pub access: Access,
+    /// Pipeline stage the access happened at.
+    pub stage: PipelineStage,
+    /// Queue family that currently owns the resource, if it has ever been
+    /// transferred between queue families.
+    pub queue_family: Option<QueueFamilyId>,
+}
+
+/// A sub-range of an `Item`'s resource whose recorded access changed from `from`
+/// to `to`; callers use this to build the `Barrier` their backend requires.
+#[derive(Debug, Clone)]
+pub struct Transition {
+    /// The affected sub-range, relative to the start of the resource.
+    pub range: Range<u64>,
+    /// Previously recorded access.
+    pub from: AccessState,
+    /// Newly recorded access.
+    pub to: AccessState,
+}
+
+impl<I, T> Item<I, T> {
+    /// Record that `range` of this resource was just accessed as `access`,
+    /// returning the `Transition`s a caller must insert barriers for.
+    pub fn record_access(&mut self, range: Range<u64>, access: AccessState) -> Vec<Transition> {
+        if range.start >= range.end {
+            return Vec::new();
+        }
+
+        let mut transitions = Vec::new();
+        let mut rebuilt = Vec::with_capacity(self.tracked.len() + 1);
+        let mut cursor = range.start;
+
+        for (old_range, old_state) in self.tracked.drain(..) {
+            if old_range.end <= range.start || old_range.start >= range.end {
+                // No overlap with the newly accessed range; keep as-is.
+                rebuilt.push((old_range, old_state));
+                continue;
+            }
+
+            // Keep the part of the old entry that falls before the new range.
+            if old_range.start < range.start {
+                rebuilt.push((old_range.start..range.start, old_state));
+            }
+
+            let overlap_start = old_range.start.max(range.start);
+            let overlap_end = old_range.end.min(range.end);
+            if cursor < overlap_start {
+                // A gap before this entry had no prior access; record it plainly.
+                rebuilt.push((cursor..overlap_start, access));
+                cursor = overlap_start;
+            }
+            if old_state != access {
+                transitions.push(Transition {
+                    range: overlap_start..overlap_end,
+                    from: old_state,
+                    to: access,
+                });
+            }
+            rebuilt.push((overlap_start..overlap_end, access));
+            cursor = overlap_end;
+
+            // Keep the part of the old entry that falls after the new range.
+            if old_range.end > range.end {
+                rebuilt.push((range.end..old_range.end, old_state));
+            }
+        }
+
+        if cursor < range.end {
+            rebuilt.push((cursor..range.end, access));
+        }
+
+        rebuilt.sort_by_key(|&(ref r, _)| r.start);
+
+        // Merge adjacent entries that ended up sharing identical state.
+        let mut merged = Vec::<(Range<u64>, AccessState)>::with_capacity(rebuilt.len());
+        for (r, state) in rebuilt {
+            if let Some(&mut (ref mut last_range, last_state)) = merged.last_mut() {
+                if last_range.end == r.start && last_state == state {
+                    last_range.end = r.end;
+                    continue;
+                }
+            }
+            merged.push((r, state));
+        }
+
+        self.tracked = merged;
+        transitions
+    }
+
+    /// Report the `Transition`s that would result from accessing `range` as
+    /// `access`, without recording the access.
+    pub fn transitions_for(&self, range: Range<u64>, access: AccessState) -> Vec<Transition> {
+        self.tracked
+            .iter()
+            .filter(|&&(ref r, _)| r.start < range.end && r.end > range.start)
+            .filter(|&&(_, state)| state != access)
+            .map(|&(ref r, state)| Transition {
+                range: r.start.max(range.start)..r.end.min(range.end),
+                from: state,
+                to: access,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(access: Access) -> AccessState {
+        AccessState {
+            access,
+            stage: PipelineStage::empty(),
+            queue_family: None,
+        }
+    }
+
+    fn item() -> Item<(), ()> {
+        Item {
+            raw: (),
+            block: (),
+            tracked: Vec::new(),
+            persistent_map: None,
+        }
+    }
+
+    #[test]
+    fn first_access_needs_no_transition() {
+        let mut item = item();
+        let transitions = item.record_access(0..16, state(Access::SHADER_READ));
+        assert!(transitions.is_empty());
+        assert_eq!(item.tracked, vec![(0..16, state(Access::SHADER_READ))]);
+    }
+
+    #[test]
+    fn adjacent_identical_access_merges() {
+        let mut item = item();
+        item.record_access(0..16, state(Access::SHADER_READ));
+        let transitions = item.record_access(16..32, state(Access::SHADER_READ));
+        assert!(transitions.is_empty());
+        assert_eq!(item.tracked, vec![(0..32, state(Access::SHADER_READ))]);
+    }
+
+    #[test]
+    fn changed_access_over_same_range_yields_one_transition() {
+        let mut item = item();
+        item.record_access(0..16, state(Access::SHADER_READ));
+        let transitions = item.record_access(0..16, state(Access::SHADER_WRITE));
+        assert_eq!(transitions.len(), 1);
+        assert_eq!(transitions[0].range, 0..16);
+        assert_eq!(transitions[0].from, state(Access::SHADER_READ));
+        assert_eq!(transitions[0].to, state(Access::SHADER_WRITE));
+        assert_eq!(item.tracked, vec![(0..16, state(Access::SHADER_WRITE))]);
+    }
+
+    #[test]
+    fn partial_overlap_splits_the_old_entry() {
+        let mut item = item();
+        item.record_access(0..32, state(Access::SHADER_READ));
+        let transitions = item.record_access(8..16, state(Access::SHADER_WRITE));
+        assert_eq!(transitions.len(), 1);
+        assert_eq!(transitions[0].range, 8..16);
+        assert_eq!(
+            item.tracked,
+            vec![
+                (0..8, state(Access::SHADER_READ)),
+                (8..16, state(Access::SHADER_WRITE)),
+                (16..32, state(Access::SHADER_READ)),
+            ]
+        );
+    }
+
+    #[test]
+    fn gap_with_no_prior_access_needs_no_transition() {
+        let mut item = item();
+        item.record_access(0..8, state(Access::SHADER_READ));
+        item.record_access(16..24, state(Access::SHADER_READ));
+        let transitions = item.record_access(0..24, state(Access::SHADER_READ));
+        assert!(transitions.is_empty());
+        assert_eq!(item.tracked, vec![(0..24, state(Access::SHADER_READ))]);
+    }
+
+    #[test]
+    fn transitions_for_does_not_record_the_access() {
+        let mut item = item();
+        item.record_access(0..16, state(Access::SHADER_READ));
+        let transitions = item.transitions_for(0..16, state(Access::SHADER_WRITE));
+        assert_eq!(transitions.len(), 1);
+        assert_eq!(item.tracked, vec![(0..16, state(Access::SHADER_READ))]);
+    }
+}
+
 impl<I, T> Borrow<I> for Item<I, T> {
     fn borrow(&self) -> &I {
         &self.raw
@@ -181,6 +678,62 @@ impl From<ImageCreationError> for FactoryError {
     }
 }
 
+/// Optional capability for exporting the platform memory object backing a
+/// `Factory`'s resources as an OS handle, and for importing such a handle in
+/// place of allocating fresh memory. Not every `Factory`/`MemoryAllocator` can
+/// support this, so it is kept separate rather than folded into `Factory`.
+///
+/// This trait describes the shape of the capability only; exporting/importing a
+/// handle is inherently backend-specific, so each backend's concrete allocator
+/// must provide its own `impl ExternalMemory` (there is no blanket impl here).
+#[cfg(unix)]
+pub trait ExternalMemory<B: Backend>: Factory<B> {
+    /// Export the platform memory handle backing `buffer`'s block as a POSIX file
+    /// descriptor (a dmabuf fd on Linux).
+    ///
+    /// The returned descriptor is a new duplicate that the caller owns and is
+    /// responsible for closing.
+    fn export_memory_fd(&self, device: &B::Device, buffer: &Self::Buffer) -> Result<RawFd, Self::Error>;
+
+    /// Create a buffer that imports `handle` instead of allocating fresh memory.
+    ///
+    /// Buffers have no tiling, so unlike `create_image_from_external` there is no
+    /// DRM format modifier to thread through here.
+    ///
+    /// The `Block` backing the returned buffer does not own `handle`: destroying
+    /// the buffer later closes `handle` but never hands it back to the allocator's
+    /// `free`, since this factory did not allocate it.
+    fn create_buffer_from_external(
+        &mut self,
+        device: &B::Device,
+        handle: RawFd,
+        size: u64,
+        usage: BufferUsage,
+    ) -> Result<Self::Buffer, Self::Error>;
+
+    /// Create an image that imports `handle` instead of allocating fresh memory.
+    ///
+    /// `modifier` carries the DRM format modifier describing the tiling/layout of
+    /// the imported allocation, for consumers (compositors, GBM-backed pipelines)
+    /// that need it to interpret the memory correctly; pass `None` for linear,
+    /// non-tiled memory.
+    ///
+    /// See `create_buffer_from_external` for the non-owning nature of the returned
+    /// resource's `Block`.
+    fn create_image_from_external(
+        &mut self,
+        device: &B::Device,
+        handle: RawFd,
+        kind: Kind,
+        level: Level,
+        format: Format,
+        tiling: Tiling,
+        usage: ImageUsage,
+        view_caps: ViewCapabilities,
+        modifier: Option<u64>,
+    ) -> Result<Self::Image, Self::Error>;
+}
+
 impl<B, A> Factory<B> for A
 where
     B: Backend,
@@ -208,6 +761,8 @@ where
         Ok(Item {
             raw: buf,
             block,
+            tracked: Vec::new(),
+            persistent_map: None,
         })
     }
 
@@ -231,16 +786,425 @@ where
         Ok(Item {
             raw: img,
             block,
+            tracked: Vec::new(),
+            persistent_map: None,
         })
     }
 
-    fn destroy_buffer(&mut self, device: &B::Device, buffer: Self::Buffer) {
+    fn destroy_buffer(&mut self, device: &B::Device, mut buffer: Self::Buffer) {
+        buffer.unmap::<B>(device);
         device.destroy_buffer(buffer.raw);
-        self.free(device, buffer.block);
+        if buffer.block.owns_memory() {
+            self.free(device, buffer.block);
+        }
     }
 
-    fn destroy_image(&mut self, device: &B::Device, image: Self::Image) {
+    fn destroy_image(&mut self, device: &B::Device, mut image: Self::Image) {
+        image.unmap::<B>(device);
         device.destroy_image(image.raw);
-        self.free(device, image.block);
+        if image.block.owns_memory() {
+            self.free(device, image.block);
+        }
     }
 }
+
+/// Extension of `Factory` that can create a buffer or image and upload initial
+/// data into it, staging through a temporary `Upload` buffer when needed.
+///
+/// Blanket-implemented for every `MemoryAllocator` whose `Request` can be built
+/// from a `MemoryUsage` hint, since staging needs to allocate its own buffer.
+pub trait FactoryInit<B: Backend>: Factory<B> {
+    /// Create a buffer and upload `data` into it, staging through a temporary
+    /// `Upload` buffer when the destination memory is not host-visible.
+    ///
+    /// ### Parameters
+    ///
+    /// - `device`: device to create the buffer on
+    /// - `request`: information needed by the `MemoryAllocator` to allocate a block
+    ///              of memory for the buffer
+    /// - `usage`: hal buffer `Usage`; `TRANSFER_DST` is added automatically
+    /// - `data`: initial contents of the buffer
+    fn create_buffer_init(
+        &mut self,
+        device: &B::Device,
+        request: Self::BufferRequest,
+        usage: BufferUsage,
+        data: &[u8],
+    ) -> Result<(Self::Buffer, Option<PendingBufferUpload<Self::Buffer>>), Self::Error>;
+
+    /// Create an image and upload `data` into it via a temporary staging buffer.
+    ///
+    /// `data` only initializes mip level 0, array layer 0; for a `kind`/`level`
+    /// with more layers or mip levels, the rest of the image is left uninitialized.
+    ///
+    /// ### Parameters
+    ///
+    /// - `device`: device to create the image on
+    /// - `request`: information needed by the `MemoryAllocator` to allocate a block
+    ///              of memory for the image
+    /// - `kind`: `Kind` of texture storage to allocate
+    /// - `level`: mipmap level
+    /// - `format`: texture format
+    /// - `usage`: hal image usage; `TRANSFER_DST` is added automatically
+    /// - `data`: initial contents of level 0, layer 0, tightly packed per `format`
+    fn create_image_init(
+        &mut self,
+        device: &B::Device,
+        request: Self::ImageRequest,
+        kind: Kind,
+        level: Level,
+        format: Format,
+        tiling: Tiling,
+        usage: ImageUsage,
+        view_caps: ViewCapabilities,
+        data: &[u8],
+    ) -> Result<(Self::Image, PendingImageUpload<Self::Buffer>), Self::Error>;
+}
+
+impl<B, A> FactoryInit<B> for A
+where
+    B: Backend,
+    A: MemoryAllocator<B>,
+    A::Request: From<MemoryUsage>,
+{
+    fn create_buffer_init(
+        &mut self,
+        device: &B::Device,
+        request: A::Request,
+        usage: BufferUsage,
+        data: &[u8],
+    ) -> Result<(Item<B::Buffer, A::Block>, Option<PendingBufferUpload<Item<B::Buffer, A::Block>>>), FactoryError>
+    {
+        let mut item = self.create_buffer(
+            device,
+            request,
+            data.len() as u64,
+            usage | BufferUsage::TRANSFER_DST,
+        )?;
+
+        if item.block.properties().contains(Properties::CPU_VISIBLE) {
+            {
+                let mut mapped = item.map::<B>(device, 0..data.len() as u64)?;
+                mapped.write()[..data.len()].copy_from_slice(data);
+                mapped.flush()?;
+            }
+            return Ok((item, None));
+        }
+
+        let staging_request = A::Request::from(MemoryUsage::Upload);
+        let mut staging = self.create_buffer(
+            device,
+            staging_request,
+            data.len() as u64,
+            BufferUsage::TRANSFER_SRC,
+        )?;
+        {
+            let mut mapped = staging.map::<B>(device, 0..data.len() as u64)?;
+            mapped.write()[..data.len()].copy_from_slice(data);
+            mapped.flush()?;
+        }
+
+        let pending = PendingBufferUpload {
+            staging,
+            regions: vec![
+                BufferCopy {
+                    src: 0,
+                    dst: 0,
+                    size: data.len() as u64,
+                },
+            ],
+        };
+
+        Ok((item, Some(pending)))
+    }
+
+    fn create_image_init(
+        &mut self,
+        device: &B::Device,
+        request: A::Request,
+        kind: Kind,
+        level: Level,
+        format: Format,
+        tiling: Tiling,
+        usage: ImageUsage,
+        view_caps: ViewCapabilities,
+        data: &[u8],
+    ) -> Result<(Item<B::Image, A::Block>, PendingImageUpload<Item<B::Buffer, A::Block>>), FactoryError>
+    {
+        let image = self.create_image(
+            device,
+            request,
+            kind,
+            level,
+            format,
+            tiling,
+            usage | ImageUsage::TRANSFER_DST,
+            view_caps,
+        )?;
+
+        let staging_request = A::Request::from(MemoryUsage::Upload);
+        let mut staging = self.create_buffer(
+            device,
+            staging_request,
+            data.len() as u64,
+            BufferUsage::TRANSFER_SRC,
+        )?;
+        {
+            let mut mapped = staging.map::<B>(device, 0..data.len() as u64)?;
+            mapped.write()[..data.len()].copy_from_slice(data);
+            mapped.flush()?;
+        }
+
+        let extent = kind.extent();
+        // `data` only covers level 0, layer 0 (see doc comment); any other levels
+        // or layers in `kind` are left uninitialized by this single region.
+        let pending = PendingImageUpload {
+            staging,
+            regions: vec![
+                gfx_hal::command::BufferImageCopy {
+                    buffer_offset: 0,
+                    buffer_width: 0,
+                    buffer_height: 0,
+                    image_layers: gfx_hal::image::SubresourceLayers {
+                        aspects: format.surface_desc().aspects,
+                        level: 0,
+                        layers: 0..1,
+                    },
+                    image_offset: gfx_hal::image::Offset { x: 0, y: 0, z: 0 },
+                    image_extent: extent,
+                },
+            ],
+        };
+
+        Ok((image, pending))
+    }
+}
+
+/// Describes one resource to be bound into a shared, aliased memory block via
+/// `create_aliased`.
+pub enum ResourceDesc {
+    /// A buffer of the given size and usage.
+    Buffer {
+        /// Size in bytes of the buffer.
+        size: u64,
+        /// hal buffer `Usage`.
+        usage: BufferUsage,
+    },
+    /// An image with the given parameters.
+    Image {
+        /// `Kind` of texture storage to allocate.
+        kind: Kind,
+        /// Mipmap level.
+        level: Level,
+        /// Texture format.
+        format: Format,
+        /// Tiling of the image.
+        tiling: Tiling,
+        /// hal image usage.
+        usage: ImageUsage,
+        /// Capabilities of the views that may be created of the image.
+        view_caps: ViewCapabilities,
+    },
+}
+
+/// A buffer or image bound into a `Block` shared with its aliasing siblings,
+/// produced by `create_aliased` at the offset it computed when packing `layout`.
+pub enum Aliased<B: Backend, T> {
+    /// An aliased buffer.
+    Buffer(Item<B::Buffer, AliasedBlock<T>>),
+    /// An aliased image.
+    Image(Item<B::Image, AliasedBlock<T>>),
+}
+
+/// A non-owning view over a range of a `Block` shared by several aliased
+/// resources.
+///
+/// ### Safety
+///
+/// Resources backed by sibling `AliasedBlock`s over overlapping ranges of the same
+/// underlying `Block` must never be in use by the device at the same time: packing
+/// them into one allocation only saves memory for resources that are genuinely
+/// transient and mutually exclusive (e.g. render targets that never live in the
+/// same frame). The allocator has no way to detect or enforce this; callers must
+/// synchronize access themselves.
+pub struct AliasedBlock<T> {
+    shared: Rc<T>,
+    range: Range<u64>,
+}
+
+impl<T: Block> Block for AliasedBlock<T> {
+    type Memory = T::Memory;
+
+    fn memory(&self) -> &T::Memory {
+        self.shared.memory()
+    }
+
+    fn range(&self) -> Range<u64> {
+        self.range.clone()
+    }
+
+    fn properties(&self) -> Properties {
+        self.shared.properties()
+    }
+
+    fn owns_memory(&self) -> bool {
+        false
+    }
+}
+
+/// Extension of `MemoryAllocator` that can bind several resources into one
+/// allocated `Block`, letting transient/mutually-exclusive resources share memory.
+///
+/// Blanket-implemented for every `MemoryAllocator`.
+pub trait AliasingAllocator<B: Backend>: MemoryAllocator<B> {
+    /// Bind several resources into one allocated `Block` at packed, non-overlapping
+    /// offsets. See `AliasedBlock` for the safety invariant the caller must uphold.
+    ///
+    /// ### Parameters
+    ///
+    /// - `device`: device to create the resources on
+    /// - `request`: information needed by the `MemoryAllocator` to allocate the one
+    ///              covering block; its `type_mask` must be compatible with every
+    ///              resource in `layout`
+    /// - `layout`: the resources to pack into the shared block
+    fn create_aliased(
+        &mut self,
+        device: &B::Device,
+        request: Self::Request,
+        layout: &[ResourceDesc],
+    ) -> Result<Vec<Aliased<B, Self::Block>>, FactoryError>;
+
+    /// Destroy one resource produced by `create_aliased`. The covering `Block` is
+    /// only actually freed once its last surviving alias has been destroyed.
+    fn destroy_aliased(&mut self, device: &B::Device, aliased: Aliased<B, Self::Block>);
+}
+
+impl<B, A> AliasingAllocator<B> for A
+where
+    B: Backend,
+    A: MemoryAllocator<B>,
+{
+    fn create_aliased(
+        &mut self,
+        device: &B::Device,
+        request: A::Request,
+        layout: &[ResourceDesc],
+    ) -> Result<Vec<Aliased<B, A::Block>>, FactoryError> {
+        // Create each raw resource up front so its true `Requirements` (size,
+        // alignment, compatible memory types) can be queried before anything is
+        // bound to memory.
+        enum Raw<B: Backend> {
+            Buffer(B::Buffer),
+            Image(B::Image),
+        }
+
+        let mut raws = Vec::with_capacity(layout.len());
+        let mut reqs = Vec::with_capacity(layout.len());
+        for desc in layout {
+            let (raw, req) = match *desc {
+                ResourceDesc::Buffer { size, usage } => {
+                    let buf = device.create_buffer(size, usage)?;
+                    let req = device.get_buffer_requirements(&buf);
+                    (Raw::<B>::Buffer(buf), req)
+                }
+                ResourceDesc::Image {
+                    kind,
+                    level,
+                    format,
+                    tiling,
+                    usage,
+                    view_caps,
+                } => {
+                    let img = device.create_image(kind, level, format, tiling, usage, view_caps)?;
+                    let req = device.get_image_requirements(&img);
+                    (Raw::<B>::Image(img), req)
+                }
+            };
+            raws.push(raw);
+            reqs.push(req);
+        }
+
+        // Pack each resource at its own aligned offset within one covering range,
+        // and intersect every `type_mask` so the single block we allocate is
+        // actually usable by all of them.
+        let mut offset = 0u64;
+        let mut offsets = Vec::with_capacity(reqs.len());
+        let mut type_mask = !0u64;
+        for req in &reqs {
+            offset = align_up(offset, req.alignment);
+            offsets.push(offset);
+            offset += req.size;
+            type_mask &= req.type_mask;
+        }
+        assert_ne!(
+            type_mask, 0,
+            "resources passed to create_aliased have no memory type in common"
+        );
+
+        let covering = Requirements {
+            size: offset,
+            alignment: reqs.iter().map(|r| r.alignment).max().unwrap_or(1),
+            type_mask,
+        };
+        let shared = Rc::new(self.alloc(device, request, covering)?);
+
+        let mut result = Vec::with_capacity(raws.len());
+        for ((raw, req), offset) in raws.into_iter().zip(&reqs).zip(offsets) {
+            let block = AliasedBlock {
+                shared: Rc::clone(&shared),
+                range: (shared.range().start + offset)..(shared.range().start + offset + req.size),
+            };
+            result.push(match raw {
+                Raw::Buffer(buf) => {
+                    let buf = device
+                        .bind_buffer_memory(block.memory(), block.range().start, buf)
+                        .unwrap();
+                    Aliased::Buffer(Item {
+                        raw: buf,
+                        block,
+                        tracked: Vec::new(),
+                        persistent_map: None,
+                    })
+                }
+                Raw::Image(img) => {
+                    let img = device
+                        .bind_image_memory(block.memory(), block.range().start, img)
+                        .unwrap();
+                    Aliased::Image(Item {
+                        raw: img,
+                        block,
+                        tracked: Vec::new(),
+                        persistent_map: None,
+                    })
+                }
+            });
+        }
+
+        Ok(result)
+    }
+
+    fn destroy_aliased(&mut self, device: &B::Device, aliased: Aliased<B, A::Block>) {
+        let shared = match aliased {
+            Aliased::Buffer(mut item) => {
+                item.unmap::<B>(device);
+                device.destroy_buffer(item.raw);
+                item.block.shared
+            }
+            Aliased::Image(mut item) => {
+                item.unmap::<B>(device);
+                device.destroy_image(item.raw);
+                item.block.shared
+            }
+        };
+        // Only the last surviving alias actually owns the covering block at this
+        // point; earlier siblings just drop their `Rc` and leave the memory alive
+        // for the rest of the group.
+        if let Ok(block) = Rc::try_unwrap(shared) {
+            self.free(device, block);
+        }
+    }
+}
+
+fn align_up(offset: u64, alignment: u64) -> u64 {
+    (offset + alignment - 1) / alignment * alignment
+}